@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use std::path::Path;
+use tokio::fs;
+
+/// Something that can suggest completions for the word under the cursor,
+/// modeled on rustyline's `Completer` trait.
+#[async_trait]
+pub trait Completer: Send + Sync {
+    /// Returns the byte offset where the word being completed starts, and
+    /// the list of candidate replacements for it.
+    async fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>);
+}
+
+/// Completes the current word against filesystem entries in its directory,
+/// using `tokio::fs` so completion never blocks the reactor.
+#[derive(Default)]
+pub struct FilenameCompleter;
+
+impl FilenameCompleter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Completer for FilenameCompleter {
+    async fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let start = line[..pos]
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let word = &line[start..pos];
+
+        let (dir, prefix) = match word.rfind('/') {
+            Some(i) => (&word[..=i], &word[i + 1..]),
+            None => ("", word),
+        };
+
+        let dir_path = if dir.is_empty() {
+            Path::new(".")
+        } else {
+            Path::new(dir)
+        };
+
+        let mut candidates = Vec::new();
+        let mut entries = match fs::read_dir(dir_path).await {
+            Ok(entries) => entries,
+            Err(_) => return (start, candidates),
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with(prefix) {
+                candidates.push(format!("{}{}", dir, name));
+            }
+        }
+
+        candidates.sort();
+        (start, candidates)
+    }
+}
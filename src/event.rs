@@ -0,0 +1,11 @@
+/// The outcome of one logical "read a line" step, returned by
+/// [`crate::Readline::run`] and yielded by [`crate::Readline::events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A line the user submitted with Enter.
+    Line(String),
+    /// The user pressed Ctrl-C.
+    CTRLC,
+    /// The reader returned zero bytes; there is nothing left to read.
+    EOF,
+}
@@ -0,0 +1,61 @@
+/// A decoded keypress, named rather than a raw byte or escape sequence so
+/// the main loop can match on `Key::Home` instead of magic numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Tab,
+    CtrlC,
+    CtrlR,
+    CtrlG,
+    CtrlK,
+    CtrlU,
+    CtrlW,
+    CtrlY,
+    /// Meta/Alt + a regular key, e.g. `ESC y` for Alt-Y.
+    Alt(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    Delete,
+    PageUp,
+    PageDown,
+    WordLeft,
+    WordRight,
+    /// A bare Escape keypress: `ESC` not followed by anything within
+    /// [`crate::ESCAPE_TIMEOUT`], as opposed to `Unknown`, which is a fully
+    /// framed CSI/SS3 sequence we just don't recognise.
+    Escape,
+    Unknown,
+}
+
+/// Decodes the parameter/final-byte pair of a CSI sequence (`ESC [ params final`)
+/// into a [`Key`]. `params` holds the `;`-separated numeric parameters seen
+/// before the final byte, e.g. `"1;5"` for a Ctrl-modified arrow.
+pub(crate) fn decode_csi(params: &str, final_byte: u8) -> Key {
+    let mut parts = params.split(';');
+    let first: Option<u32> = parts.next().and_then(|p| p.parse().ok());
+    let modifier: Option<u32> = parts.next().and_then(|p| p.parse().ok());
+    let ctrl = modifier == Some(5);
+
+    match (final_byte, first) {
+        (b'A', _) => Key::Up,
+        (b'B', _) => Key::Down,
+        (b'C', _) if ctrl => Key::WordRight,
+        (b'C', _) => Key::Right,
+        (b'D', _) if ctrl => Key::WordLeft,
+        (b'D', _) => Key::Left,
+        (b'H', _) => Key::Home,
+        (b'F', _) => Key::End,
+        (b'~', Some(1)) => Key::Home,
+        (b'~', Some(3)) => Key::Delete,
+        (b'~', Some(4)) => Key::End,
+        (b'~', Some(5)) => Key::PageUp,
+        (b'~', Some(6)) => Key::PageDown,
+        _ => Key::Unknown,
+    }
+}
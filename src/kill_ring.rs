@@ -0,0 +1,80 @@
+/// Which side of the cursor a kill removed text from. Consecutive kills in
+/// the same direction are merged into the top ring entry rather than each
+/// pushing a new one, matching GNU readline's "kill run" behaviour.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KillDirection {
+    Forward,
+    Backward,
+}
+
+/// A bounded ring of killed (cut) text, modeled on rustyline/emacs's
+/// kill-ring, with a yank pointer that Alt-Y rotates through.
+pub(crate) struct KillRing {
+    ring: Vec<String>,
+    yank_idx: usize,
+    last_direction: Option<KillDirection>,
+    capacity: usize,
+}
+
+impl KillRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: Vec::new(),
+            yank_idx: 0,
+            last_direction: None,
+            capacity,
+        }
+    }
+
+    /// Records a kill. If the previous kill was in the same direction, text
+    /// is merged into the top entry (appended for a forward kill, prepended
+    /// for a backward one) instead of pushing a new ring entry.
+    pub fn kill(&mut self, text: String, direction: KillDirection) {
+        if text.is_empty() {
+            return;
+        }
+
+        if self.last_direction == Some(direction) {
+            if let Some(top) = self.ring.last_mut() {
+                match direction {
+                    KillDirection::Forward => top.push_str(&text),
+                    KillDirection::Backward => top.insert_str(0, &text),
+                }
+                self.yank_idx = self.ring.len() - 1;
+                return;
+            }
+        }
+
+        self.ring.push(text);
+        while self.ring.len() > self.capacity {
+            self.ring.remove(0);
+        }
+        self.yank_idx = self.ring.len() - 1;
+        self.last_direction = Some(direction);
+    }
+
+    /// Breaks the "same direction" chain, called whenever a non-kill edit
+    /// happens so the next kill starts a fresh ring entry.
+    pub fn reset_direction(&mut self) {
+        self.last_direction = None;
+    }
+
+    pub fn top(&self) -> Option<&str> {
+        self.ring.get(self.yank_idx).map(String::as_str)
+    }
+
+    /// Rotates to the previous ring entry (Alt-Y) and returns it.
+    pub fn rotate(&mut self) -> Option<&str> {
+        if self.ring.is_empty() {
+            return None;
+        }
+
+        self.yank_idx = if self.yank_idx == 0 {
+            self.ring.len() - 1
+        } else {
+            self.yank_idx - 1
+        };
+
+        self.ring.get(self.yank_idx).map(String::as_str)
+    }
+}
@@ -1,19 +1,63 @@
 use crossterm::terminal;
 use std::path::Path;
+use std::time::Duration;
 use tokio::{
     fs::OpenOptions,
     io::{self, AsyncRead, AsyncReadExt, AsyncWriteExt},
     sync::{Mutex, RwLock},
+    time::timeout,
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+mod completion;
+mod event;
+mod key;
+mod kill_ring;
+mod search;
+
+use async_stream::stream;
+use futures_core::Stream;
+
+pub use completion::{Completer, FilenameCompleter};
+pub use event::Event;
+use key::Key;
+use kill_ring::{KillDirection, KillRing};
+use search::SearchState;
+
+/// How many entries the kill ring keeps before evicting the oldest.
+const KILL_RING_CAPACITY: usize = 16;
+
+/// How long to wait for the byte after an `ESC` before giving up on it being
+/// the start of a CSI/SS3/Alt sequence and treating it as a bare Escape.
+/// Terminals emit a whole escape sequence as one burst, so a real multi-byte
+/// sequence always clears this; a lone Escape keypress never sends anything
+/// more, so without this timeout `read_escape_sequence` would block forever
+/// waiting for a continuation byte that's never coming.
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(25);
 
 pub struct Readline<R> {
     prompt: RwLock<String>,
     history: RwLock<Vec<String>>,
     history_pos: RwLock<usize>, // Add history position tracking
     current_input: RwLock<String>,
-    ci_pos: RwLock<usize>,
+    ci_pos: RwLock<usize>, // grapheme index into current_input, not a byte offset
     reader: Mutex<R>,
     history_file: Option<Mutex<tokio::fs::File>>,
+    completer: RwLock<Option<Box<dyn Completer>>>,
+    // Candidates from the previous Tab press, kept so a second consecutive
+    // Tab that yields the same candidates prints the full list.
+    pending_completion: RwLock<Option<Vec<String>>>,
+    // Some(..) while a Ctrl-R incremental search is in progress.
+    search: RwLock<Option<SearchState>>,
+    kill_ring: RwLock<KillRing>,
+    // Byte range in `current_input` of the text inserted by the most recent
+    // Ctrl-Y/Alt-Y, so a following Alt-Y knows what to replace.
+    last_yank: RwLock<Option<(usize, usize)>>,
+    // How many physical terminal rows, and which one the cursor was left
+    // on, the last render used — so `clear_current_line` can undo it.
+    last_render_rows: RwLock<usize>,
+    last_cursor_row: RwLock<usize>,
 }
 
 impl<R: AsyncRead + Unpin> Readline<R> {
@@ -67,6 +111,13 @@ impl<R: AsyncRead + Unpin> Readline<R> {
                 )),
                 None => None,
             },
+            completer: RwLock::new(None),
+            pending_completion: RwLock::new(None),
+            search: RwLock::new(None),
+            kill_ring: RwLock::new(KillRing::new(KILL_RING_CAPACITY)),
+            last_yank: RwLock::new(None),
+            last_render_rows: RwLock::new(1),
+            last_cursor_row: RwLock::new(0),
         };
 
         readline.history_load().await.unwrap();
@@ -81,67 +132,347 @@ impl<R: AsyncRead + Unpin> Readline<R> {
         terminal::disable_raw_mode()
     }
 
-    pub async fn run(&self) -> std::io::Result<String> {
+    pub async fn run(&self) -> std::io::Result<Event> {
         let _ = self.print_current_line().await;
 
         loop {
-            let k = self.get_keycode().await?;
+            let k = match self.get_key().await {
+                Ok(k) => k,
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(Event::EOF),
+                Err(e) => return Err(e),
+            };
+
+            if self.search.read().await.is_some() || matches!(k, Key::CtrlR) {
+                if let Some(line) = self.handle_search_key(k).await? {
+                    return Ok(Event::Line(line));
+                }
+                continue;
+            }
+
+            // Any key other than Tab breaks a "double Tab lists candidates"
+            // sequence; Tab manages `pending_completion` itself.
+            if !matches!(k, Key::Tab) {
+                *self.pending_completion.write().await = None;
+            }
+
+            // Consecutive kills in the same direction (Ctrl-K forward,
+            // Ctrl-U/W backward) merge into one ring entry; any other key
+            // starts a fresh entry on the next kill.
+            if !matches!(k, Key::CtrlK | Key::CtrlU | Key::CtrlW) {
+                self.kill_ring.write().await.reset_direction();
+            }
+
+            // Alt-Y only makes sense right after a Ctrl-Y/Alt-Y yank.
+            if !matches!(k, Key::CtrlY | Key::Alt('y')) {
+                *self.last_yank.write().await = None;
+            }
 
             match k {
-                // CTRL + c
-                3 => {
-                    break;
+                Key::CtrlC => {
+                    return Ok(Event::CTRLC);
                 }
-                // Control code
-                27 => {
-                    let _ = self.get_keycode().await?;
-                    let k = self.get_keycode().await?;
-                    match k {
-                        65 => {
-                            self.on_up_arrow().await?;
-                        }
-                        66 => {
-                            self.on_down_arrow().await?;
-                        }
-                        67 => {
-                            self.on_right_arrow().await?;
-                        }
-                        68 => {
-                            self.on_left_arrow().await?;
-                        }
-                        _ => { /*break;*/ }
-                    }
+                Key::Tab => {
+                    self.on_tab().await?;
                 }
-                126 => {
-                    self.on_canc().await?;
+                Key::Enter => {
+                    return self.on_enter().await.map(Event::Line);
                 }
-                127 => {
+                Key::Backspace => {
                     self.on_backspace().await?;
                 }
-                13 => {
-                    return self.on_enter().await;
+                Key::Delete => {
+                    self.on_canc().await?;
+                }
+                Key::Up => {
+                    self.on_up_arrow().await?;
+                }
+                Key::Down => {
+                    self.on_down_arrow().await?;
+                }
+                Key::Left => {
+                    self.on_left_arrow().await?;
+                }
+                Key::Right => {
+                    self.on_right_arrow().await?;
+                }
+                Key::Home => {
+                    self.on_home().await?;
+                }
+                Key::End => {
+                    self.on_end().await?;
+                }
+                Key::WordLeft => {
+                    self.on_word_left().await?;
+                }
+                Key::WordRight => {
+                    self.on_word_right().await?;
                 }
-                _ => {
-                    self.insert_ci(k as char).await?;
+                Key::CtrlK => {
+                    self.on_ctrl_k().await?;
                 }
+                Key::CtrlU => {
+                    self.on_ctrl_u().await?;
+                }
+                Key::CtrlW => {
+                    self.on_ctrl_w().await?;
+                }
+                Key::CtrlY => {
+                    self.on_ctrl_y().await?;
+                }
+                Key::Alt('y') => {
+                    self.on_yank_rotate().await?;
+                }
+                Key::Char(c) => {
+                    self.insert_ci(c).await?;
+                }
+                // PageUp/PageDown are decoded but nothing consumes them yet;
+                // Unknown covers any CSI sequence we don't recognise, and
+                // Escape a bare Escape keypress; neither has a binding
+                // outside of search. Other Alt-prefixed keys have no
+                // binding either. CtrlR is always handled above, before
+                // this match; CtrlG only acts inside a search (also handled
+                // above) and does nothing here. Both still need an arm for
+                // exhaustiveness.
+                Key::PageUp
+                | Key::PageDown
+                | Key::CtrlR
+                | Key::CtrlG
+                | Key::Alt(_)
+                | Key::Escape
+                | Key::Unknown => {}
             }
         }
+    }
 
-        Err(std::io::Error::new(std::io::ErrorKind::Other, "Exited"))
+    /// Adapts repeated [`Self::run`] calls into a single stream, so callers
+    /// can `while let Some(ev) = stream.next().await` or drive it alongside
+    /// other sources with `tokio::select!` instead of awaiting one line at
+    /// a time. Each item shares this same `Readline`, so history,
+    /// completion and kill-ring state all carry over between lines. The
+    /// stream ends after yielding `Event::EOF` or an `Err`; `Event::CTRLC`
+    /// does not end it, matching `run`'s own behaviour of just returning
+    /// control to the caller.
+    pub fn events(&self) -> impl Stream<Item = std::io::Result<Event>> + '_ {
+        stream! {
+            loop {
+                let ev = self.run().await;
+                let done = matches!(ev, Err(_) | Ok(Event::EOF));
+                yield ev;
+                if done {
+                    break;
+                }
+            }
+        }
     }
 
-    async fn get_keycode(&self) -> Result<u8, io::Error> {
-        let mut buffer = [0u8; 1];
+    /// Reads one keypress and decodes it into a [`Key`], expanding `ESC`
+    /// into a full CSI/SS3 escape sequence instead of the two raw bytes the
+    /// loop used to consume unconditionally.
+    async fn get_key(&self) -> io::Result<Key> {
+        let c = self.get_keycode().await?;
+
+        Ok(match c {
+            '\x03' => Key::CtrlC,
+            '\r' => Key::Enter,
+            '\x7F' => Key::Backspace,
+            '\t' => Key::Tab,
+            '\x12' => Key::CtrlR,
+            '\x07' => Key::CtrlG,
+            '\x0B' => Key::CtrlK,
+            '\x15' => Key::CtrlU,
+            '\x17' => Key::CtrlW,
+            '\x19' => Key::CtrlY,
+            '\x1B' => return self.read_escape_sequence().await,
+            other => Key::Char(other),
+        })
+    }
 
+    async fn read_raw_byte(&self) -> io::Result<u8> {
+        let mut buffer = [0u8; 1];
         self.reader.lock().await.read_exact(&mut buffer).await?;
         Ok(buffer[0])
     }
 
+    /// Parses what follows an `ESC`: a CSI sequence (`[...`), an SS3
+    /// sequence (`O` + one letter, used by some terminals for Home/End), or
+    /// a Meta/Alt-prefixed key (`ESC` + any other byte, e.g. Alt-Y).
+    async fn read_escape_sequence(&self) -> io::Result<Key> {
+        let introducer = match timeout(ESCAPE_TIMEOUT, self.read_raw_byte()).await {
+            Ok(result) => result?,
+            Err(_) => return Ok(Key::Escape),
+        };
+
+        match introducer {
+            b'[' => self.read_csi_sequence().await,
+            b'O' => {
+                let final_byte = self.read_raw_byte().await?;
+                Ok(match final_byte {
+                    b'H' => Key::Home,
+                    b'F' => Key::End,
+                    _ => Key::Unknown,
+                })
+            }
+            other => Ok(Key::Alt(other as char)),
+        }
+    }
+
+    /// Accumulates the `;`-separated numeric parameters of a CSI sequence
+    /// until the final (non-digit, non-`;`) byte, then decodes them.
+    async fn read_csi_sequence(&self) -> io::Result<Key> {
+        let mut params = String::new();
+
+        loop {
+            let b = self.read_raw_byte().await?;
+
+            if b.is_ascii_digit() || b == b';' {
+                params.push(b as char);
+                continue;
+            }
+
+            return Ok(key::decode_csi(&params, b));
+        }
+    }
+
+    async fn on_home(&self) -> io::Result<()> {
+        let mut ci_pos = self.ci_pos.write().await;
+
+        if *ci_pos != 0 {
+            *ci_pos = 0;
+            std::mem::drop(ci_pos);
+
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+        }
+
+        Ok(())
+    }
+
+    async fn on_end(&self) -> io::Result<()> {
+        let ci = self.current_input.read().await;
+        let mut ci_pos = self.ci_pos.write().await;
+        let len = ci.graphemes(true).count();
+
+        if *ci_pos != len {
+            *ci_pos = len;
+            std::mem::drop(ci_pos);
+            std::mem::drop(ci);
+
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+        }
+
+        Ok(())
+    }
+
+    async fn on_word_left(&self) -> io::Result<()> {
+        let ci = self.current_input.read().await;
+        let mut ci_pos = self.ci_pos.write().await;
+        let graphemes: Vec<&str> = ci.graphemes(true).collect();
+
+        let mut pos = *ci_pos;
+        while pos > 0 && graphemes[pos - 1].chars().all(char::is_whitespace) {
+            pos -= 1;
+        }
+        while pos > 0 && !graphemes[pos - 1].chars().all(char::is_whitespace) {
+            pos -= 1;
+        }
+
+        if pos != *ci_pos {
+            *ci_pos = pos;
+            std::mem::drop(ci_pos);
+            std::mem::drop(ci);
+
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+        }
+
+        Ok(())
+    }
+
+    async fn on_word_right(&self) -> io::Result<()> {
+        let ci = self.current_input.read().await;
+        let mut ci_pos = self.ci_pos.write().await;
+        let graphemes: Vec<&str> = ci.graphemes(true).collect();
+        let len = graphemes.len();
+
+        let mut pos = *ci_pos;
+        while pos < len && graphemes[pos].chars().all(char::is_whitespace) {
+            pos += 1;
+        }
+        while pos < len && !graphemes[pos].chars().all(char::is_whitespace) {
+            pos += 1;
+        }
+
+        if pos != *ci_pos {
+            *ci_pos = pos;
+            std::mem::drop(ci_pos);
+            std::mem::drop(ci);
+
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next keypress, reassembling it into a full Unicode scalar
+    /// value. Terminals emit multi-byte UTF-8 sequences as consecutive bytes
+    /// on the same read, so a non-ASCII char needs 1-4 bytes stitched
+    /// together rather than a single byte cast straight to `char`.
+    async fn get_keycode(&self) -> Result<char, io::Error> {
+        let mut buffer = [0u8; 1];
+        self.reader.lock().await.read_exact(&mut buffer).await?;
+        let first = buffer[0];
+
+        let len = if first & 0x80 == 0 {
+            1
+        } else if first & 0xE0 == 0xC0 {
+            2
+        } else if first & 0xF0 == 0xE0 {
+            3
+        } else if first & 0xF8 == 0xF0 {
+            4
+        } else {
+            1
+        };
+
+        let mut bytes = vec![first];
+        for _ in 1..len {
+            let mut b = [0u8; 1];
+            self.reader.lock().await.read_exact(&mut b).await?;
+            bytes.push(b[0]);
+        }
+
+        Ok(std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
     async fn insert_ci(&self, what: char) -> io::Result<()> {
         self.ci_insert_pos(what).await;
 
-        if *self.ci_pos.read().await != self.current_input.read().await.len() {
-            Self::clear_current_line().await?;
+        if *self.ci_pos.read().await != self.current_input.read().await.graphemes(true).count() {
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+            return Ok(());
+        }
+
+        // Still at the end of the buffer, but the new character may have
+        // pushed prompt+input past the terminal width and onto a new row;
+        // the single-char fast path below can't update last_render_rows/
+        // last_cursor_row itself, so fall back to a full redraw whenever
+        // that row count would actually change.
+        let prompt = self.get_prompt().await;
+        let ci = self.current_input.read().await;
+        let term_width = Self::terminal_width().await;
+        let total_width = UnicodeWidthStr::width(format!("{}{}", prompt, *ci).as_str());
+        std::mem::drop(ci);
+
+        let (row, _) = Self::row_col_for_width(total_width, term_width, false);
+
+        if row + 1 != *self.last_render_rows.read().await {
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         } else {
             Self::write_flush(format!("{}", what)).await?;
@@ -157,7 +488,7 @@ impl<R: AsyncRead + Unpin> Readline<R> {
             *ci_pos -= 1;
             std::mem::drop(ci_pos);
 
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         }
 
@@ -168,12 +499,12 @@ impl<R: AsyncRead + Unpin> Readline<R> {
         let ci = self.current_input.read().await;
         let mut ci_pos = self.ci_pos.write().await;
 
-        if *ci_pos < ci.len() {
+        if *ci_pos < ci.graphemes(true).count() {
             *ci_pos += 1;
             std::mem::drop(ci_pos);
             std::mem::drop(ci);
 
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         }
 
@@ -190,7 +521,7 @@ impl<R: AsyncRead + Unpin> Readline<R> {
             std::mem::drop(hp);
             std::mem::drop(history);
 
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         }
 
@@ -208,7 +539,7 @@ impl<R: AsyncRead + Unpin> Readline<R> {
             std::mem::drop(hp);
             std::mem::drop(history);
 
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         }
 
@@ -236,7 +567,7 @@ impl<R: AsyncRead + Unpin> Readline<R> {
 
     async fn on_backspace(&self) -> io::Result<()> {
         if self.ci_remove_pos().await {
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
             let _ = self.print_current_line().await;
         }
 
@@ -245,13 +576,376 @@ impl<R: AsyncRead + Unpin> Readline<R> {
 
     async fn on_canc(&self) -> io::Result<()> {
         if self.ci_remove_pos_right().await {
-            Self::clear_current_line().await?;
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+        }
+
+        Ok(())
+    }
+
+    /// Ctrl-K: cuts from the cursor to the end of the line.
+    async fn on_ctrl_k(&self) -> io::Result<()> {
+        let mut ci = self.current_input.write().await;
+        let ci_pos = *self.ci_pos.read().await;
+        let start = Self::grapheme_byte_offset(&ci, ci_pos);
+
+        if start >= ci.len() {
+            return Ok(());
+        }
+
+        let cut = ci.split_off(start);
+        std::mem::drop(ci);
+
+        self.kill_ring.write().await.kill(cut, KillDirection::Forward);
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Ctrl-U: cuts from the start of the line to the cursor.
+    async fn on_ctrl_u(&self) -> io::Result<()> {
+        let mut ci = self.current_input.write().await;
+        let mut ci_pos = self.ci_pos.write().await;
+        let end = Self::grapheme_byte_offset(&ci, *ci_pos);
+
+        if end == 0 {
+            return Ok(());
+        }
+
+        let cut: String = ci.drain(..end).collect();
+        *ci_pos = 0;
+        std::mem::drop(ci);
+        std::mem::drop(ci_pos);
+
+        self.kill_ring.write().await.kill(cut, KillDirection::Backward);
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Ctrl-W: cuts the whitespace-delimited word to the left of the cursor.
+    async fn on_ctrl_w(&self) -> io::Result<()> {
+        let mut ci = self.current_input.write().await;
+        let mut ci_pos = self.ci_pos.write().await;
+
+        let mut pos = *ci_pos;
+        {
+            let graphemes: Vec<&str> = ci.graphemes(true).collect();
+            while pos > 0 && graphemes[pos - 1].chars().all(char::is_whitespace) {
+                pos -= 1;
+            }
+            while pos > 0 && !graphemes[pos - 1].chars().all(char::is_whitespace) {
+                pos -= 1;
+            }
+        }
+
+        if pos == *ci_pos {
+            return Ok(());
+        }
+
+        let start = Self::grapheme_byte_offset(&ci, pos);
+        let end = Self::grapheme_byte_offset(&ci, *ci_pos);
+        let cut: String = ci.drain(start..end).collect();
+        *ci_pos = pos;
+        std::mem::drop(ci);
+        std::mem::drop(ci_pos);
+
+        self.kill_ring.write().await.kill(cut, KillDirection::Backward);
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Ctrl-Y: yanks the top of the kill ring at the cursor.
+    async fn on_ctrl_y(&self) -> io::Result<()> {
+        let text = match self.kill_ring.read().await.top() {
+            Some(text) => text.to_string(),
+            None => return Ok(()),
+        };
+
+        let mut ci = self.current_input.write().await;
+        let mut ci_pos = self.ci_pos.write().await;
+        let start = Self::grapheme_byte_offset(&ci, *ci_pos);
+
+        ci.insert_str(start, &text);
+        let end = start + text.len();
+        *ci_pos += text.graphemes(true).count();
+
+        std::mem::drop(ci);
+        std::mem::drop(ci_pos);
+
+        *self.last_yank.write().await = Some((start, end));
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Alt-Y: rotates the kill ring and replaces the just-yanked text with
+    /// the previous entry.
+    async fn on_yank_rotate(&self) -> io::Result<()> {
+        let Some((start, end)) = *self.last_yank.read().await else {
+            return Ok(());
+        };
+
+        let Some(text) = self.kill_ring.write().await.rotate().map(str::to_string) else {
+            return Ok(());
+        };
+
+        let mut ci = self.current_input.write().await;
+        ci.replace_range(start..end, &text);
+        let new_end = start + text.len();
+        *self.ci_pos.write().await = ci[..new_end].graphemes(true).count();
+        std::mem::drop(ci);
+
+        *self.last_yank.write().await = Some((start, new_end));
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Starts a new Ctrl-R search, or, if one is already active, advances
+    /// past the current match so the next scan finds an older one.
+    async fn on_ctrl_r(&self) -> io::Result<()> {
+        let entering = self.search.read().await.is_none();
+
+        if entering {
+            let history_len = self.history.read().await.len();
+            let saved_input = self.current_input.read().await.clone();
+            let saved_ci_pos = *self.ci_pos.read().await;
+            *self.search.write().await = Some(SearchState::new(
+                history_len,
+                saved_input,
+                saved_ci_pos,
+            ));
+        } else {
+            let mut search = self.search.write().await;
+            if let Some(state) = search.as_mut() {
+                if let Some((idx, _)) = state.current_match {
+                    state.search_idx = idx;
+                }
+            }
+        }
+
+        self.rescan_search().await;
+        self.print_search_line().await
+    }
+
+    async fn rescan_search(&self) {
+        let mut search = self.search.write().await;
+        let Some(state) = search.as_mut() else {
+            return;
+        };
+
+        let history = self.history.read().await;
+        state.current_match = search::find_match(&history, &state.query, state.search_idx);
+    }
+
+    /// Renders the `(reverse-i-search)` prompt and the currently matched
+    /// history entry (or the pre-search buffer, if nothing matches yet),
+    /// with the cursor placed at the match.
+    async fn print_search_line(&self) -> io::Result<()> {
+        let search = self.search.read().await;
+        let Some(state) = search.as_ref() else {
+            return Ok(());
+        };
+
+        let history = self.history.read().await;
+        let displayed = match state.current_match {
+            Some((idx, _)) => history[idx].clone(),
+            None => state.saved_input.clone(),
+        };
+        let prompt = format!("(reverse-i-search)`{}': ", state.query);
+
+        self.clear_current_line().await?;
+
+        let term_width = Self::terminal_width().await;
+        let full = format!("{}{}", prompt, displayed);
+
+        let mut stderr = tokio::io::stderr();
+        stderr.write_all(format!("\r{}", full).as_bytes()).await?;
+        stderr.flush().await?;
+
+        let total_width = UnicodeWidthStr::width(full.as_str());
+        let (last_row, _) = Self::row_col_for_width(total_width, term_width, false);
+        *self.last_render_rows.write().await = last_row + 1;
+
+        let cursor_width = UnicodeWidthStr::width(prompt.as_str())
+            + match state.current_match {
+                Some((_, byte_pos)) => UnicodeWidthStr::width(&displayed[..byte_pos]),
+                None => 0,
+            };
+        let (cursor_row, cursor_col) =
+            Self::row_col_for_width(cursor_width, term_width, cursor_width < total_width);
+        *self.last_cursor_row.write().await = cursor_row;
+
+        Self::move_cursor(cursor_row as i64 - last_row as i64, cursor_col).await?;
+
+        Ok(())
+    }
+
+    async fn cancel_search(&self) -> io::Result<()> {
+        if let Some(state) = self.search.write().await.take() {
+            *self.current_input.write().await = state.saved_input;
+            *self.ci_pos.write().await = state.saved_ci_pos;
+        }
+
+        self.clear_current_line().await?;
+        let _ = self.print_current_line().await;
+
+        Ok(())
+    }
+
+    /// Routes a keypress while a Ctrl-R search is active (or being started).
+    /// Returns `Some(line)` once Enter accepts a match, ending `run`.
+    async fn handle_search_key(&self, key: Key) -> io::Result<Option<String>> {
+        match key {
+            Key::CtrlR => {
+                self.on_ctrl_r().await?;
+            }
+            // A bare Escape cancels the search the same way Ctrl-G does.
+            // An unrecognised-but-complete CSI/SS3 sequence (Key::Unknown)
+            // is left to the catch-all below, same as outside search.
+            Key::CtrlG | Key::Escape => {
+                self.cancel_search().await?;
+            }
+            Key::Backspace => {
+                {
+                    let mut search = self.search.write().await;
+                    if let Some(state) = search.as_mut() {
+                        state.query.pop();
+                        state.search_idx = self.history.read().await.len();
+                    }
+                }
+
+                self.rescan_search().await;
+                self.print_search_line().await?;
+            }
+            Key::Enter => {
+                let accepted = {
+                    let search = self.search.read().await;
+                    let state = search.as_ref().expect("search active");
+                    let history = self.history.read().await;
+
+                    match state.current_match {
+                        Some((idx, _)) => history[idx].clone(),
+                        None => state.saved_input.clone(),
+                    }
+                };
+
+                *self.ci_pos.write().await = accepted.graphemes(true).count();
+                *self.current_input.write().await = accepted;
+                *self.search.write().await = None;
+
+                return self.on_enter().await.map(Some);
+            }
+            Key::Char(c) => {
+                {
+                    let mut search = self.search.write().await;
+                    if let Some(state) = search.as_mut() {
+                        state.query.push(c);
+                    }
+                }
+
+                self.rescan_search().await;
+                self.print_search_line().await?;
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    async fn on_tab(&self) -> io::Result<()> {
+        let (start, candidates) = {
+            let completer = self.completer.read().await;
+            let Some(completer) = completer.as_ref() else {
+                return Ok(());
+            };
+
+            let ci = self.current_input.read().await.clone();
+            let byte_pos = Self::grapheme_byte_offset(&ci, *self.ci_pos.read().await);
+            completer.complete(&ci, byte_pos).await
+        };
+
+        if candidates.is_empty() {
+            return Ok(());
+        }
+
+        let byte_pos = {
+            let ci = self.current_input.read().await;
+            Self::grapheme_byte_offset(&ci, *self.ci_pos.read().await)
+        };
+
+        if candidates.len() == 1 {
+            self.replace_word(start, byte_pos, &candidates[0]).await;
+            self.clear_current_line().await?;
+            let _ = self.print_current_line().await;
+            return Ok(());
+        }
+
+        let mut pending = self.pending_completion.write().await;
+        let is_second_tab = pending.as_deref() == Some(candidates.as_slice());
+
+        if is_second_tab {
+            std::mem::drop(pending);
+
+            Self::write_flush("\r\n".to_string()).await?;
+            Self::write_flush(candidates.join("  ")).await?;
+            Self::write_flush("\r\n".to_string()).await?;
             let _ = self.print_current_line().await;
+        } else {
+            *pending = Some(candidates.clone());
+            std::mem::drop(pending);
+
+            let common_prefix = Self::longest_common_prefix(&candidates);
+            if common_prefix.len() > byte_pos - start {
+                self.replace_word(start, byte_pos, &common_prefix).await;
+                self.clear_current_line().await?;
+                let _ = self.print_current_line().await;
+            }
         }
 
         Ok(())
     }
 
+    /// Replaces the `start..end` byte range of `current_input` with
+    /// `replacement` and moves the cursor to just past it.
+    async fn replace_word(&self, start: usize, end: usize, replacement: &str) {
+        let mut ci = self.current_input.write().await;
+        ci.replace_range(start..end, replacement);
+
+        *self.ci_pos.write().await = ci[..start + replacement.len()].graphemes(true).count();
+    }
+
+    fn longest_common_prefix(candidates: &[String]) -> String {
+        let mut iter = candidates.iter();
+        let Some(first) = iter.next() else {
+            return String::new();
+        };
+
+        let mut prefix_len = first.chars().count();
+        for candidate in iter {
+            let common = first
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            prefix_len = prefix_len.min(common);
+        }
+
+        first.chars().take(prefix_len).collect()
+    }
+
     async fn _current_input_pop(&self) {
         self.current_input.write().await.pop();
     }
@@ -262,9 +956,17 @@ impl<R: AsyncRead + Unpin> Readline<R> {
 
     async fn ci_insert_pos(&self, what: char) {
         let mut ci_pos = self.ci_pos.write().await;
+        let mut ci = self.current_input.write().await;
+
+        let byte_pos = Self::grapheme_byte_offset(&ci, *ci_pos);
+        ci.insert(byte_pos, what);
 
-        self.current_input.write().await.insert(*ci_pos, what);
-        *ci_pos += 1;
+        // A combining mark merges into the preceding grapheme cluster
+        // instead of starting a new one, so the grapheme count doesn't
+        // necessarily grow by 1 just because a char was inserted; recompute
+        // it from the buffer instead of assuming `+= 1`.
+        let new_byte_pos = byte_pos + what.len_utf8();
+        *ci_pos = ci[..new_byte_pos].graphemes(true).count();
     }
 
     // Returns where to update the current line or not
@@ -278,31 +980,47 @@ impl<R: AsyncRead + Unpin> Readline<R> {
         }
 
         *ci_pos -= 1;
-        ci.remove(*ci_pos);
+        let start = Self::grapheme_byte_offset(&ci, *ci_pos);
+        let end = Self::grapheme_byte_offset(&ci, *ci_pos + 1);
+        ci.replace_range(start..end, "");
 
-        return true;
+        true
     }
 
     // Returns where to update the current line or not
     async fn ci_remove_pos_right(&self) -> bool {
         let mut ci = self.current_input.write().await;
         let ci_pos = self.ci_pos.read().await;
+        let grapheme_count = ci.graphemes(true).count();
 
         // If there is nothing to delete or the position is already at the extreme right.
-        if ci.is_empty() || *ci_pos == ci.len() {
+        if ci.is_empty() || *ci_pos == grapheme_count {
             return false;
         }
 
-        ci.remove(*ci_pos);
+        let start = Self::grapheme_byte_offset(&ci, *ci_pos);
+        let end = Self::grapheme_byte_offset(&ci, *ci_pos + 1);
+        ci.replace_range(start..end, "");
 
         true
     }
 
     async fn set_ci(&self, what: String) {
-        *self.ci_pos.write().await = what.len();
+        *self.ci_pos.write().await = what.graphemes(true).count();
         *self.current_input.write().await = what;
     }
 
+    /// Maps a grapheme-cluster index into `s` to the corresponding byte
+    /// offset, so cursor math can stay in "characters on screen" while
+    /// string mutation stays in bytes. `grapheme_idx` equal to the grapheme
+    /// count (the cursor sitting past the last char) maps to `s.len()`.
+    fn grapheme_byte_offset(s: &str, grapheme_idx: usize) -> usize {
+        s.grapheme_indices(true)
+            .nth(grapheme_idx)
+            .map(|(offset, _)| offset)
+            .unwrap_or_else(|| s.len())
+    }
+
     async fn reset_history_pos(&self) {
         *self.history_pos.write().await = self.history.read().await.len(); // Reset history position
                                                                            // History file truncate
@@ -346,15 +1064,32 @@ impl<R: AsyncRead + Unpin> Readline<R> {
         Ok(())
     }
 
+    /// Redraws the prompt + current buffer, wrapping at the terminal width
+    /// instead of assuming a single physical row. Records how many rows the
+    /// render used and which one the cursor landed on, so a following
+    /// `clear_current_line` can undo exactly this render.
     async fn print_current_line(&self) -> std::io::Result<()> {
-        let mut stderr = tokio::io::stderr();
         let prompt = self.get_prompt().await;
+        let ci = self.current_input.read().await;
+        let term_width = Self::terminal_width().await;
+        let full = format!("{}{}", prompt, ci);
 
-        stderr
-            .write_all(format!("\r{}{}", prompt, self.current_input.read().await).as_bytes())
-            .await?;
+        let mut stderr = tokio::io::stderr();
+        stderr.write_all(format!("\r{}", full).as_bytes()).await?;
         stderr.flush().await?;
-        Self::move_cursor_col(prompt.len() + *self.ci_pos.read().await + 1).await?;
+
+        let total_width = UnicodeWidthStr::width(full.as_str());
+        let (last_row, _) = Self::row_col_for_width(total_width, term_width, false);
+        *self.last_render_rows.write().await = last_row + 1;
+
+        let byte_pos = Self::grapheme_byte_offset(&ci, *self.ci_pos.read().await);
+        let cursor_width =
+            UnicodeWidthStr::width(prompt.as_str()) + UnicodeWidthStr::width(&ci[..byte_pos]);
+        let (cursor_row, cursor_col) =
+            Self::row_col_for_width(cursor_width, term_width, cursor_width < total_width);
+        *self.last_cursor_row.write().await = cursor_row;
+
+        Self::move_cursor(cursor_row as i64 - last_row as i64, cursor_col).await?;
 
         Ok(())
     }
@@ -368,15 +1103,138 @@ impl<R: AsyncRead + Unpin> Readline<R> {
         *prompt = new_prompt;
     }
 
-    async fn _move_cursor(row: usize, col: usize) -> std::io::Result<()> {
-        Self::write_flush(format!("\x1B[{};{}H", row, col)).await
+    /// Installs (or replaces) the tab completer. Pass `None` to disable
+    /// completion.
+    pub async fn set_completer(&self, completer: Option<Box<dyn Completer>>) {
+        *self.completer.write().await = completer;
+    }
+
+    /// Moves the cursor `row_delta` rows up (negative) or down (positive)
+    /// from its current position, then to the given 1-indexed column. We
+    /// only ever know the cursor's position relative to where the current
+    /// render started, not its absolute position on screen, so this is a
+    /// relative row move combined with an absolute column move rather than
+    /// a single `CUP` escape.
+    async fn move_cursor(row_delta: i64, col: usize) -> std::io::Result<()> {
+        match row_delta.cmp(&0) {
+            std::cmp::Ordering::Less => {
+                Self::write_flush(format!("\x1B[{}A", -row_delta)).await?;
+            }
+            std::cmp::Ordering::Greater => {
+                Self::write_flush(format!("\x1B[{}B", row_delta)).await?;
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+
+        Self::move_cursor_col(col).await
     }
 
     async fn move_cursor_col(col: usize) -> std::io::Result<()> {
         Self::write_flush(format!("\x1B[{}G", col)).await
     }
 
-    async fn clear_current_line() -> std::io::Result<()> {
-        Self::write_flush("\x1B[2K\r".to_string()).await
+    /// Undoes the previous `print_current_line`/`print_search_line` render:
+    /// moves up to its first row, clears every row it used, and leaves the
+    /// cursor at that first row ready for a fresh render.
+    async fn clear_current_line(&self) -> std::io::Result<()> {
+        let rows = *self.last_render_rows.read().await;
+        let cursor_row = *self.last_cursor_row.read().await;
+
+        if cursor_row > 0 {
+            Self::write_flush(format!("\x1B[{}A", cursor_row)).await?;
+        }
+
+        for i in 0..rows {
+            Self::write_flush("\r\x1B[2K".to_string()).await?;
+            if i + 1 < rows {
+                Self::write_flush("\x1B[1B".to_string()).await?;
+            }
+        }
+
+        if rows > 1 {
+            Self::write_flush(format!("\x1B[{}A", rows - 1)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn terminal_width() -> usize {
+        terminal::size()
+            .map(|(cols, _)| cols as usize)
+            .unwrap_or(80)
+            .max(1)
+    }
+
+    /// The (0-indexed row, 1-indexed column) a cursor sits at after `width`
+    /// printed display columns, wrapping at `term_width` columns per row.
+    ///
+    /// A terminal only "pending-wraps" the column right after the very
+    /// last character it was given: filling a row exactly leaves the
+    /// cursor parked at its last column, and only the *next* character
+    /// actually pushes it onto a new row. That lazy rounding is correct
+    /// for `width` values that describe the end of everything printed
+    /// (`eager == false`), but any *interior* position — a cursor with
+    /// more buffer still to come after it — has already had that next
+    /// character printed, so one that lands exactly on a row boundary has
+    /// already wrapped to the start of the following row. Pass
+    /// `eager == true` for those interior positions.
+    fn row_col_for_width(width: usize, term_width: usize, eager: bool) -> (usize, usize) {
+        if term_width == 0 || width == 0 {
+            return (0, width + 1);
+        }
+
+        if eager && width.is_multiple_of(term_width) {
+            return (width / term_width, 1);
+        }
+
+        let row = (width - 1) / term_width;
+        let col = width - row * term_width;
+        (row, col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Readline;
+
+    // `row_col_for_width` doesn't touch `R`, but it's an associated fn of
+    // the generic `Readline<R>`, so calling it needs some concrete
+    // `AsyncRead + Unpin` type to instantiate with; `tokio::io::Empty` is
+    // the lightest one available.
+    type RowColReadline = Readline<tokio::io::Empty>;
+
+    #[test]
+    fn lazy_wrap_parks_at_last_column_when_nothing_follows() {
+        // The final width of a 10-column buffer in a 5-column terminal:
+        // exactly filling row 0, with nothing printed after it, should
+        // leave the cursor parked on row 0's last column (pending wrap),
+        // not rolled onto row 1.
+        assert_eq!(RowColReadline::row_col_for_width(5, 5, false), (0, 5));
+        assert_eq!(RowColReadline::row_col_for_width(10, 5, false), (1, 5));
+    }
+
+    #[test]
+    fn eager_wrap_rolls_to_next_row_when_more_text_follows() {
+        // Same boundary, but as an interior cursor position with trailing
+        // text still to print: the row that text occupies has already
+        // been started, so the cursor belongs at its first column.
+        assert_eq!(RowColReadline::row_col_for_width(5, 5, true), (1, 1));
+        assert_eq!(RowColReadline::row_col_for_width(10, 5, true), (2, 1));
+    }
+
+    #[test]
+    fn non_boundary_widths_are_unaffected_by_eager_wrap() {
+        // Eager wrap only changes anything exactly on a row boundary;
+        // everywhere else lazy and eager rounding agree.
+        assert_eq!(
+            RowColReadline::row_col_for_width(7, 5, false),
+            RowColReadline::row_col_for_width(7, 5, true)
+        );
+    }
+
+    #[test]
+    fn zero_width_or_term_width_stays_on_row_zero_without_wrapping() {
+        assert_eq!(RowColReadline::row_col_for_width(0, 5, false), (0, 1));
+        assert_eq!(RowColReadline::row_col_for_width(3, 0, false), (0, 4));
     }
 }
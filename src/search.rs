@@ -0,0 +1,36 @@
+/// State for an in-progress Ctrl-R incremental reverse search: the query
+/// typed so far, how far back into `history` the next scan should start,
+/// and the buffer/cursor to restore if the search is cancelled.
+pub(crate) struct SearchState {
+    pub query: String,
+    pub search_idx: usize,
+    pub saved_input: String,
+    pub saved_ci_pos: usize,
+    pub current_match: Option<(usize, usize)>,
+}
+
+impl SearchState {
+    pub fn new(history_len: usize, saved_input: String, saved_ci_pos: usize) -> Self {
+        Self {
+            query: String::new(),
+            search_idx: history_len,
+            saved_input,
+            saved_ci_pos,
+            current_match: None,
+        }
+    }
+}
+
+/// Scans `history[..from]` backwards for the first entry containing `query`
+/// as a substring, returning its index and the byte offset of the match.
+pub(crate) fn find_match(history: &[String], query: &str, from: usize) -> Option<(usize, usize)> {
+    if query.is_empty() {
+        return None;
+    }
+
+    history[..from.min(history.len())]
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, entry)| entry.find(query).map(|byte_pos| (i, byte_pos)))
+}
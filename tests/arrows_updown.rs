@@ -5,15 +5,15 @@ use fakestdin::FakeStdin;
 
 #[tokio::test]
 async fn test_arrows_updown() {
-    // \x7E is CANC
+    // \x1B[3~ is Delete (real CSI sequence: ESC [ 3 ~)
     // \x03 is CTRL+C
     // \r is ENTER
-    // \x1B\x01\x41 is up arrow
-    // \x1B\x01\x42 is down arrow
-    // \x1B\x01\x43 is rigth arrow
-    // \x1B\x01\x44 is left arrow
+    // \x1B[A is up arrow
+    // \x1B[B is down arrow
+    // \x1B[C is right arrow
+    // \x1B[D is left arrow
 
-    let input = b"test command -r one\rnot the previous command\r\x1B\x01\x41\x1B\x01\x41\x1B\x01\x42\r\x03";
+    let input = b"test command -r one\rnot the previous command\r\x1B[A\x1B[A\x1B[B\r\x03";
     let fake_stdin = FakeStdin::new(input);
 
     let rl = Readline::new(fake_stdin, "arrows updown > ", None).await;
@@ -0,0 +1,25 @@
+use readline::{Event, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+#[tokio::test]
+async fn test_combining_mark_keeps_cursor_grapheme_aligned() {
+    // "e" + U+0301 (combining acute accent) merges into the single
+    // grapheme "é", so `ci_pos` must land on 2 (a, é) rather than 3 (as a
+    // flat "one char in, one position forward" count would give). Typing
+    // "cd" after it, moving Left twice and inserting "X" checks that: if
+    // `ci_pos` were inflated by the combining mark, the cursor would sit
+    // one grapheme further right than intended and "X" would land between
+    // "c" and "d" instead of between "\u{e9}" and "c".
+    let input = "ae\u{0301}cd\x1B[D\x1B[DX\r\x03".as_bytes();
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "combining > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("ae\u{0301}Xcd".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
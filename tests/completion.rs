@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use readline::{Completer, Event, FilenameCompleter, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+struct WordCompleter {
+    words: Vec<&'static str>,
+}
+
+#[async_trait]
+impl Completer for WordCompleter {
+    async fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let start = line[..pos]
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let prefix = &line[start..pos];
+
+        let candidates = self
+            .words
+            .iter()
+            .filter(|w| w.starts_with(prefix))
+            .map(|w| w.to_string())
+            .collect();
+
+        (start, candidates)
+    }
+}
+
+#[tokio::test]
+async fn test_single_candidate_completes_the_word() {
+    let input = b"std::col\t\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "completion > ", None).await;
+    rl.set_completer(Some(Box::new(WordCompleter {
+        words: vec!["std::collections", "std::convert"],
+    })))
+    .await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("std::collections".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_ambiguous_candidates_complete_to_common_prefix() {
+    let input = b"std::co\t\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "completion > ", None).await;
+    rl.set_completer(Some(Box::new(WordCompleter {
+        words: vec!["std::collections", "std::convert"],
+    })))
+    .await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("std::co".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_filename_completer_lists_matching_entries_in_a_directory() {
+    let dir = std::env::temp_dir().join(format!(
+        "readline_filename_completer_test_{}",
+        std::process::id()
+    ));
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    tokio::fs::write(dir.join("report.txt"), b"").await.unwrap();
+    tokio::fs::write(dir.join("report.csv"), b"").await.unwrap();
+    tokio::fs::write(dir.join("notes.md"), b"").await.unwrap();
+
+    let completer = FilenameCompleter::new();
+    let line = format!("cat {}/rep", dir.display());
+    let pos = line.len();
+
+    let (start, mut candidates) = completer.complete(&line, pos).await;
+    candidates.sort();
+
+    assert_eq!(start, 4);
+    assert_eq!(
+        candidates,
+        vec![
+            format!("{}/report.csv", dir.display()),
+            format!("{}/report.txt", dir.display()),
+        ]
+    );
+
+    tokio::fs::remove_dir_all(&dir).await.unwrap();
+}
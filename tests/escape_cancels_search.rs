@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use readline::{Event, Readline};
+use tokio::time::timeout;
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+/// A lone Escape (with no following bytes, as a real terminal sends when
+/// the user just taps Esc) must cancel an in-progress Ctrl-R search rather
+/// than blocking forever waiting for a CSI/SS3 continuation byte that will
+/// never arrive. `FakeStdin` always reports EOF once its buffer is
+/// exhausted, so it can't reproduce a read that genuinely stays pending;
+/// `tokio::io::duplex` keeps the read half pending until we write more (or
+/// drop the write half), which is what it takes to catch this hang.
+#[tokio::test]
+async fn test_lone_escape_cancels_search_instead_of_hanging() {
+    let (mut writer, reader) = tokio::io::duplex(64);
+
+    let rl = Readline::new(reader, "search > ", None).await;
+
+    let run = tokio::spawn(async move { rl.run().await });
+
+    tokio::io::AsyncWriteExt::write_all(&mut writer, b"\x12\x1B")
+        .await
+        .unwrap();
+
+    // Wait out the escape-continuation timeout before sending the next
+    // key, so it lands after the search has already been cancelled
+    // instead of being swallowed as part of the (nonexistent) escape
+    // sequence.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    tokio::io::AsyncWriteExt::write_all(&mut writer, b"\x03")
+        .await
+        .unwrap();
+
+    let result = timeout(Duration::from_millis(300), run)
+        .await
+        .expect("a lone Escape should cancel the search instead of hanging")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, Event::CTRLC);
+}
+
+/// An unrecognised-but-complete CSI sequence (`ESC [ Z`, shift-tab) is not a
+/// bare Escape: it must be swallowed like any other unbound key and leave
+/// the search active, instead of cancelling it the way `Key::Escape` does.
+#[tokio::test]
+async fn test_unrecognized_csi_sequence_does_not_cancel_search() {
+    // \x1B[Z (shift-tab) is a fully-framed but unrecognised CSI sequence; it
+    // must be swallowed like any other unbound key mid-search, leaving the
+    // search (and its current match) active for the following Enter.
+    let input = b"one two\r\x12tw\x1B[Z\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "search > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("one two".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::Line("one two".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
@@ -0,0 +1,47 @@
+use readline::{Event, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+#[tokio::test]
+async fn test_home_end_delete() {
+    // \x1B[H is Home, \x1B[F is End, \x1B[3~ is Delete.
+    // Start at the end of "helloworld", jump Home, jump End, jump Home again,
+    // then Delete the leading "hello" one character at a time.
+    let input = b"helloworld\x1B[H\x1B[F\x1B[H\x1B[3~\x1B[3~\x1B[3~\x1B[3~\x1B[3~\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "escape keys > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("world".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_ctrl_arrows_word_movement() {
+    // \x1B[1;5D / \x1B[1;5C are Ctrl-Left / Ctrl-Right (word movement).
+    // Jump back two words from the end of "one two three" and insert "TWO " there.
+    let input = b"one two three\x1B[1;5D\x1B[1;5DTWO \r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "escape keys > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("one TWO two three".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_unrecognized_escape_sequence_is_swallowed() {
+    // \x1B[Z is an unrecognised CSI sequence (shift-tab); it must be
+    // swallowed without eating the "x" that follows it.
+    let input = b"ab\x1B[Zx\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "escape keys > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("abx".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
@@ -0,0 +1,45 @@
+use futures_util::StreamExt;
+use readline::{Event, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+#[tokio::test]
+async fn test_events_yields_a_line_per_enter_then_ctrlc() {
+    let input = b"foo\rbar\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "events > ", None).await;
+    let mut stream = std::pin::pin!(rl.events());
+
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Event::Line("foo".to_string())
+    );
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Event::Line("bar".to_string())
+    );
+    assert_eq!(stream.next().await.unwrap().unwrap(), Event::CTRLC);
+
+    // Event::CTRLC doesn't end the stream: the next poll drives another
+    // `run`, which here hits the end of input.
+    assert_eq!(stream.next().await.unwrap().unwrap(), Event::EOF);
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_events_ends_after_eof() {
+    let input = b"foo\r";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "events > ", None).await;
+    let mut stream = std::pin::pin!(rl.events());
+
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Event::Line("foo".to_string())
+    );
+    assert_eq!(stream.next().await.unwrap().unwrap(), Event::EOF);
+    assert!(stream.next().await.is_none());
+}
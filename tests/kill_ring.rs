@@ -0,0 +1,78 @@
+use readline::{Event, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+#[tokio::test]
+async fn test_ctrl_k_at_line_start_then_ctrl_y_restores_the_line() {
+    // \x0B is Ctrl-K (kill to end of line), \x19 is Ctrl-Y (yank).
+    // "hello world" -> Home -> Ctrl-K cuts the whole line -> End -> Ctrl-Y pastes it back.
+    let input = b"hello world\x1B[H\x0B\x1B[F\x19\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "kill ring > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("hello world".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_ctrl_u_kills_to_start_of_line() {
+    // \x15 is Ctrl-U (kill from start of line to cursor).
+    let input = b"hello world\x15\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "kill ring > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_ctrl_w_kills_previous_word_and_yank_restores_it() {
+    // \x17 is Ctrl-W (kill word left). Remove "world", then yank it back.
+    let input = b"hello world\x17\x19\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "kill ring > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("hello world".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_alt_y_rotates_to_the_older_ring_entry() {
+    // Kill two distinct spans ("world" via Ctrl-W, then "hello " via
+    // Ctrl-K after a Home resets the kill-merge chain), yank the most
+    // recent one, then Alt-Y (\x1By) should swap in the older entry
+    // instead of the one Ctrl-Y already pasted.
+    let input = b"hello world\x17\x1B[H\x0B\x19\x1Byy\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "kill ring > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("worldy".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_consecutive_kills_merge_into_one_ring_entry() {
+    // Two consecutive Ctrl-W kills should merge into a single ring entry, so
+    // a single Ctrl-Y restores both words at once.
+    let input = b"one two three\x17\x17\x19\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "kill ring > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("one two three".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
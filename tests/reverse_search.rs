@@ -0,0 +1,54 @@
+use readline::{Event, Readline};
+
+mod fakestdin;
+use fakestdin::FakeStdin;
+
+#[tokio::test]
+async fn test_reverse_search_finds_history_entry() {
+    // \x12 is Ctrl-R (enter/advance reverse search), \x07 is Ctrl-G (cancel).
+    let input = b"foo bar\rbaz qux\r\x12foo\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "search > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("foo bar".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::Line("baz qux".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::Line("foo bar".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_reverse_search_steps_to_older_match_on_repeated_ctrl_r() {
+    // Two history entries both contain "command"; the second Ctrl-R should
+    // skip the more recent one and land on the older one.
+    let input = b"first command\rsecond command\r\x12command\x12\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "search > ", None).await;
+
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("first command".to_string())
+    );
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("second command".to_string())
+    );
+    assert_eq!(
+        rl.run().await.unwrap(),
+        Event::Line("first command".to_string())
+    );
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
+
+#[tokio::test]
+async fn test_reverse_search_ctrl_g_restores_original_buffer() {
+    let input = b"foo bar\rpartial\x12foo\x07\r\x03";
+    let fake_stdin = FakeStdin::new(input);
+
+    let rl = Readline::new(fake_stdin, "search > ", None).await;
+
+    assert_eq!(rl.run().await.unwrap(), Event::Line("foo bar".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::Line("partial".to_string()));
+    assert_eq!(rl.run().await.unwrap(), Event::CTRLC);
+}
@@ -7,7 +7,7 @@ use fakestdin::FakeStdin;
 
 #[tokio::test]
 async fn test_simple() {
-    let input = b"test command -r test\rthis is the second command -m 123\r\x1B\x01\x41\r\x03";
+    let input = b"test command -r test\rthis is the second command -m 123\r\x1B[A\r\x03";
     let fake_stdin = FakeStdin::new(input);
 
     let rl = Readline::new(
@@ -36,7 +36,7 @@ async fn test_simple() {
 
     std::mem::drop(rl);
 
-    let input = b"\x1B\x01\x41\r";
+    let input = b"\x1B[A\r";
     let fake_stdin = FakeStdin::new(input);
     let rl = Readline::new(
         fake_stdin,